@@ -1,20 +1,111 @@
 use std::fs::OpenOptions;
 use std::io::BufRead;
 use std::io::BufReader;
+use std::pin::Pin;
+use std::time::Duration;
 
 #[cfg(feature = "aws-s3")]
 use std::io::Cursor;
 use std::io::ErrorKind;
 
 use async_trait::async_trait;
-
+use tokio::io::{AsyncRead, AsyncReadExt};
 
 #[cfg(feature = "aws-s3")]
 use aws_sdk_s3::primitives::ByteStream;
 
+#[cfg(feature = "aws-s3")]
+use aws_sdk_s3::types::{CompletedMultipartUpload, CompletedPart, Delete, ObjectIdentifier};
+
 #[cfg(feature = "aws-s3")]
 use aws_config::SdkConfig;
 
+// S3 requires every part except the last to be at least 5 MiB.
+#[cfg(feature = "aws-s3")]
+const DEFAULT_MULTIPART_CHUNK_SIZE: usize = 8 * 1024 * 1024;
+
+// Exponential backoff (with jitter) for AwsS3FileProvider's network calls.
+#[cfg(feature = "aws-s3")]
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    pub max_attempts: u32,
+    pub base_delay: Duration,
+    pub multiplier: f64,
+    pub jitter: f64,
+    pub max_elapsed_time: Duration,
+}
+
+#[cfg(feature = "aws-s3")]
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            max_attempts: 5,
+            base_delay: Duration::from_millis(100),
+            multiplier: 2.0,
+            jitter: 0.2,
+            max_elapsed_time: Duration::from_secs(30),
+        }
+    }
+}
+
+#[cfg(feature = "aws-s3")]
+fn is_retryable<E: aws_sdk_s3::error::ProvideErrorMetadata>(err: &aws_sdk_s3::error::SdkError<E>) -> bool {
+    use aws_sdk_s3::error::SdkError;
+
+    match err {
+        SdkError::TimeoutError(_) | SdkError::DispatchFailure(_) => true,
+        SdkError::ResponseError(context) => context.raw().status().is_server_error(),
+        SdkError::ServiceError(context) => {
+            let code = context.err().code().unwrap_or_default();
+            matches!(code, "SlowDown" | "RequestTimeout" | "InternalError" | "ServiceUnavailable" | "RequestTimeTooSkewed")
+                || context.raw().status().is_server_error()
+        }
+        _ => false,
+    }
+}
+
+// Retries `op` under `policy` until it succeeds, a non-retryable error is hit, or the
+// attempt/elapsed-time budget runs out.
+#[cfg(feature = "aws-s3")]
+async fn with_retry<T, E, F, Fut>(policy: &RetryPolicy, mut op: F) -> Result<T, Error>
+where
+    E: aws_sdk_s3::error::ProvideErrorMetadata,
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = Result<T, aws_sdk_s3::error::SdkError<E>>>,
+{
+    let started = std::time::Instant::now();
+    let mut delay = policy.base_delay;
+    let max_attempts = policy.max_attempts.max(1);
+
+    for attempt in 1..=max_attempts {
+        match op().await {
+            Ok(value) => return Ok(value),
+            Err(err) => {
+                if !is_retryable(&err) || attempt == max_attempts || started.elapsed() >= policy.max_elapsed_time {
+                    return Err(Error::new(&err.to_string()));
+                }
+
+                let jittered = delay.mul_f64(1.0 + rand::random::<f64>() * policy.jitter);
+                tokio::time::sleep(jittered).await;
+
+                delay = delay.mul_f64(policy.multiplier);
+            }
+        }
+    }
+
+    unreachable!("max_attempts is clamped to at least 1, so the loop above always returns")
+}
+
+// The Range header is inclusive of its end byte, but `read_range`'s `end` is exclusive
+// (matching LocalFileProvider's `[start, end)` window), so subtract one.
+#[cfg(feature = "aws-s3")]
+fn range_header(start: u64, end: Option<u64>) -> String {
+    match end {
+        Some(end) => format!("bytes={}-{}", start, end.saturating_sub(1)),
+        None => format!("bytes={}-", start),
+    }
+}
+
 #[derive(Debug)]
 pub struct FileProviderError {
     details: String,
@@ -51,6 +142,20 @@ impl<T> From<aws_sdk_s3::error::SdkError<T>> for FileProviderError {
     }
 }
 
+#[cfg(feature = "aws-s3")]
+impl From<aws_sdk_s3::presigning::PresigningConfigError> for FileProviderError {
+    fn from(err: aws_sdk_s3::presigning::PresigningConfigError) -> Self {
+        return FileProviderError::new(&err.to_string());
+    }
+}
+
+#[cfg(feature = "aws-s3")]
+impl From<aws_smithy_types::error::operation::BuildError> for FileProviderError {
+    fn from(err: aws_smithy_types::error::operation::BuildError) -> Self {
+        return FileProviderError::new(&err.to_string());
+    }
+}
+
 type Error = FileProviderError;
 
 #[derive(Debug)]
@@ -59,6 +164,12 @@ pub struct FileEntry {
     pub size: u64,
 }
 
+#[derive(Debug, Clone, Copy)]
+pub enum PresignMethod {
+    Get,
+    Put,
+}
+
 #[async_trait]
 pub trait FileProvider : Send + Sync {    
     async fn write(&self, path: &str, content: &str) -> Result<(), Error>;
@@ -66,9 +177,22 @@ pub trait FileProvider : Send + Sync {
     async fn read_file_buffer(&self, path: &str) -> Result<Box<dyn BufRead>, Error>;
     async fn delete_file(&self, path: &str) -> Result<(), Error>;
 
+    // end is exclusive; None reads to EOF.
+    async fn read_range(&self, path: &str, start: u64, end: Option<u64>) -> Result<Box<dyn BufRead>, Error>;
+
+    // Streams the contents of path without buffering the whole file in memory.
+    async fn read_stream(&self, path: &str) -> Result<Pin<Box<dyn AsyncRead + Send>>, Error>;
+    // Streams reader into path without buffering the whole file in memory.
+    async fn write_stream(&self, path: &str, reader: Pin<Box<dyn AsyncRead + Send>>) -> Result<(), Error>;
+
+    // Time-limited URL for a direct client GET or PUT of path.
+    async fn presign(&self, path: &str, method: PresignMethod, expires_in: Duration) -> Result<String, Error>;
+
     async fn read_dir(&self, path: &str) -> Result<Vec<FileEntry>, Error>;
     async fn list_dir(&self, path: &str) -> Result<Vec<String>, Error>;
     async fn create_dir(&self, path: &str) -> Result<(), Error>;
+    // Recursively removes everything under path.
+    async fn delete_dir(&self, path: &str) -> Result<(), Error>;
 
     async fn move_file(&self, file_path: &str, ending_path: &str, delete: bool) -> Result<(), Error>;
 
@@ -83,6 +207,9 @@ pub struct LocalFileProvider {
 #[cfg(feature = "aws-s3")]
 pub struct AwsS3FileProvider {
     pub bucket: String,
+    pub prefix: String,
+    pub retry_policy: RetryPolicy,
+    pub multipart_chunk_size: usize,
     client: aws_sdk_s3::Client,
 }
 
@@ -91,9 +218,205 @@ impl AwsS3FileProvider {
     pub async fn new(bucket: String, config: &SdkConfig) -> AwsS3FileProvider {
         return AwsS3FileProvider {
             bucket: bucket,
+            prefix: String::new(),
+            retry_policy: RetryPolicy::default(),
+            multipart_chunk_size: DEFAULT_MULTIPART_CHUNK_SIZE,
+            client: aws_sdk_s3::Client::new(config)
+        };
+    }
+
+    pub async fn new_with_prefix(bucket: String, prefix: String, config: &SdkConfig) -> AwsS3FileProvider {
+        return AwsS3FileProvider {
+            bucket: bucket,
+            prefix: prefix,
+            retry_policy: RetryPolicy::default(),
+            multipart_chunk_size: DEFAULT_MULTIPART_CHUNK_SIZE,
             client: aws_sdk_s3::Client::new(config)
         };
     }
+
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    // Must stay >= 5 MiB, S3's part-size minimum.
+    pub fn with_multipart_chunk_size(mut self, multipart_chunk_size: usize) -> Self {
+        self.multipart_chunk_size = multipart_chunk_size;
+        self
+    }
+
+    // Prepends the provider's key prefix to a caller-supplied path.
+    fn key(&self, path: &str) -> String {
+        [&self.prefix, path].join("")
+    }
+
+    async fn create_multipart_upload(&self, key: &str) -> Result<String, Error> {
+        let create = with_retry(&self.retry_policy, || {
+            self.client.create_multipart_upload().bucket(self.bucket.clone()).key(key).send()
+        }).await?;
+
+        create.upload_id()
+            .map(|id| id.to_string())
+            .ok_or_else(|| Error::new("S3 did not return an upload id for the multipart upload"))
+    }
+
+    // Aborts the upload on failure so no orphaned parts keep accruing storage charges.
+    async fn finish_multipart_upload(&self, key: &str, upload_id: &str, parts: Result<Vec<CompletedPart>, Error>) -> Result<(), Error> {
+        let result = match parts {
+            Ok(parts) => {
+                with_retry(&self.retry_policy, || {
+                    self.client.complete_multipart_upload()
+                        .bucket(self.bucket.clone())
+                        .key(key)
+                        .upload_id(upload_id)
+                        .multipart_upload(CompletedMultipartUpload::builder().set_parts(Some(parts.clone())).build())
+                        .send()
+                }).await.map(|_| ())
+            }
+            Err(e) => Err(e),
+        };
+
+        if let Err(e) = result {
+            let abort_result = with_retry(&self.retry_policy, || {
+                self.client.abort_multipart_upload().bucket(self.bucket.clone()).key(key).upload_id(upload_id).send()
+            }).await;
+
+            if let Err(abort_err) = abort_result {
+                eprintln!("Failed to abort multipart upload for {}: {:?}", key, abort_err);
+            }
+
+            return Err(e);
+        }
+
+        Ok(())
+    }
+
+    async fn write_file_multipart(&self, key: &str, contents: Vec<u8>) -> Result<(), Error> {
+        let upload_id = self.create_multipart_upload(key).await?;
+        let parts = self.upload_parts(key, &upload_id, &contents).await;
+        self.finish_multipart_upload(key, &upload_id, parts).await
+    }
+
+    async fn upload_parts(&self, key: &str, upload_id: &str, contents: &[u8]) -> Result<Vec<CompletedPart>, Error> {
+        let mut parts = Vec::new();
+
+        for (index, chunk) in contents.chunks(self.multipart_chunk_size.max(1)).enumerate() {
+            let part_number = (index + 1) as i32;
+
+            let resp = with_retry(&self.retry_policy, || {
+                self.client.upload_part()
+                    .bucket(self.bucket.clone())
+                    .key(key)
+                    .upload_id(upload_id)
+                    .part_number(part_number)
+                    .body(ByteStream::from(chunk.to_vec()))
+                    .send()
+            }).await?;
+
+            let e_tag = resp.e_tag()
+                .ok_or_else(|| Error::new(&format!("S3 did not return an ETag for part {}", part_number)))?;
+
+            parts.push(CompletedPart::builder().e_tag(e_tag).part_number(part_number).build());
+        }
+
+        Ok(parts)
+    }
+
+    // `first_chunk` is the lookahead `write_stream` already read to pick this vs. put_object.
+    async fn write_stream_multipart(&self, key: &str, first_chunk: Vec<u8>, mut reader: Pin<Box<dyn AsyncRead + Send>>) -> Result<(), Error> {
+        let upload_id = self.create_multipart_upload(key).await?;
+        let parts = self.upload_stream_parts(key, &upload_id, first_chunk, &mut reader).await;
+        self.finish_multipart_upload(key, &upload_id, parts).await
+    }
+
+    async fn upload_stream_parts(&self, key: &str, upload_id: &str, first_chunk: Vec<u8>, reader: &mut Pin<Box<dyn AsyncRead + Send>>) -> Result<Vec<CompletedPart>, Error> {
+        let mut parts = Vec::new();
+        let mut part_number = 1;
+        let mut pending = Some(first_chunk);
+
+        loop {
+            let buf = if let Some(buf) = pending.take() {
+                buf
+            } else {
+                let mut buf = vec![0u8; self.multipart_chunk_size.max(1)];
+                let mut filled = 0;
+
+                while filled < buf.len() {
+                    let read = reader.read(&mut buf[filled..]).await?;
+                    if read == 0 {
+                        break;
+                    }
+                    filled += read;
+                }
+
+                if filled == 0 {
+                    break;
+                }
+
+                buf.truncate(filled);
+                buf
+            };
+
+            let resp = with_retry(&self.retry_policy, || {
+                self.client.upload_part()
+                    .bucket(self.bucket.clone())
+                    .key(key)
+                    .upload_id(upload_id)
+                    .part_number(part_number)
+                    .body(ByteStream::from(buf.clone()))
+                    .send()
+            }).await?;
+
+            let e_tag = resp.e_tag()
+                .ok_or_else(|| Error::new(&format!("S3 did not return an ETag for part {}", part_number)))?;
+
+            parts.push(CompletedPart::builder().e_tag(e_tag).part_number(part_number).build());
+
+            part_number += 1;
+        }
+
+        Ok(parts)
+    }
+}
+
+pub struct FileSystem;
+
+impl FileSystem {
+    // file:///path -> LocalFileProvider; s3://bucket/prefix -> AwsS3FileProvider (aws-s3 feature).
+    pub async fn from_str(uri: &str) -> Result<Box<dyn FileProvider>, Error> {
+        let (scheme, rest) = uri.split_once("://")
+            .ok_or_else(|| Error::new(&format!("Missing scheme in storage URI: {}", uri)))?;
+
+        match scheme {
+            "file" => {
+                if rest.is_empty() {
+                    return Err(Error::new(&format!("file:// URI is missing a path: {}", uri)));
+                }
+
+                Ok(Box::new(LocalFileProvider { base: rest.to_string() }))
+            }
+            "s3" => {
+                #[cfg(feature = "aws-s3")]
+                {
+                    let mut parts = rest.splitn(2, '/');
+                    let bucket = match parts.next() {
+                        Some(bucket) if !bucket.is_empty() => bucket.to_string(),
+                        _ => return Err(Error::new(&format!("s3:// URI is missing a bucket name: {}", uri))),
+                    };
+                    let prefix = parts.next().unwrap_or("").to_string();
+
+                    let config = aws_config::load_from_env().await;
+                    Ok(Box::new(AwsS3FileProvider::new_with_prefix(bucket, prefix, &config).await))
+                }
+                #[cfg(not(feature = "aws-s3"))]
+                {
+                    Err(Error::new(&format!("s3:// URIs require the \"aws-s3\" feature to be enabled: {}", uri)))
+                }
+            }
+            other => Err(Error::new(&format!("Unsupported storage URI scheme \"{}\": {}", other, uri))),
+        }
+    }
 }
 
 #[async_trait]
@@ -116,6 +439,48 @@ impl FileProvider for LocalFileProvider {
         Ok(buffer)
     }
 
+    async fn read_range(&self, path: &str, start: u64, end: Option<u64>) -> Result<Box<dyn BufRead>, Error> {
+        use std::io::{Read, Seek, SeekFrom};
+
+        let path = [&self.base, path].join("");
+
+        let mut file_desc = match OpenOptions::new().read(true).open(&path) {
+            Ok(file) => file,
+            Err(e) => return Err(Error::new(&format!("File opening error: {}\nPath: {}", e.to_string(), path))),
+        };
+
+        file_desc.seek(SeekFrom::Start(start))?;
+
+        let reader: Box<dyn BufRead> = match end {
+            Some(end) => Box::new(BufReader::new(file_desc.take(end.saturating_sub(start)))),
+            None => Box::new(BufReader::new(file_desc)),
+        };
+
+        Ok(reader)
+    }
+
+    async fn read_stream(&self, path: &str) -> Result<Pin<Box<dyn AsyncRead + Send>>, Error> {
+        let path = [&self.base, path].join("");
+
+        let file = tokio::fs::File::open(&path).await
+            .map_err(|e| Error::new(&format!("File opening error: {}\nPath: {}", e, path)))?;
+
+        Ok(Box::pin(file))
+    }
+
+    async fn write_stream(&self, path: &str, mut reader: Pin<Box<dyn AsyncRead + Send>>) -> Result<(), Error> {
+        let path = [&self.base, path].join("");
+
+        let mut file = tokio::fs::File::create(&path).await?;
+        tokio::io::copy(&mut reader, &mut file).await?;
+
+        Ok(())
+    }
+
+    async fn presign(&self, _path: &str, _method: PresignMethod, _expires_in: Duration) -> Result<String, Error> {
+        Err(Error::new("LocalFileProvider does not support presigned URLs"))
+    }
+
     async fn move_file(&self, file_path: &str, ending_path: &str, delete: bool) -> Result<(), Error> {
         //use std::fs::rename;
         
@@ -191,6 +556,11 @@ impl FileProvider for LocalFileProvider {
 
         Ok(())
     }
+
+    async fn delete_dir(&self, path: &str) -> Result<(), Error> {
+        std::fs::remove_dir_all([&self.base, path].join(""))?;
+        Ok(())
+    }
 }
 
 
@@ -198,11 +568,14 @@ impl FileProvider for LocalFileProvider {
 #[cfg(feature = "aws-s3")]
 impl FileProvider for AwsS3FileProvider {
     async fn read_file_buffer(&self, path: &str) -> Result<Box<dyn BufRead>, Error> {
-        let resp = match self.client.get_object().bucket(self.bucket.clone()).key(path).send().await {
+        let key = self.key(path);
+        let resp = match with_retry(&self.retry_policy, || {
+            self.client.get_object().bucket(self.bucket.clone()).key(&key).send()
+        }).await {
             Ok(out) => out,
             Err(e) => {
-                eprintln!("Failure reading the file buffer: {:?}, Bucket: {}, Key: {}", e, self.bucket, path);
-                return Err(Error::new(e.to_string().as_ref()));
+                eprintln!("Failure reading the file buffer: {}, Bucket: {}, Key: {}", e, self.bucket, key);
+                return Err(e);
             }
         };
 
@@ -220,54 +593,166 @@ impl FileProvider for AwsS3FileProvider {
         };
     }
 
+    async fn read_range(&self, path: &str, start: u64, end: Option<u64>) -> Result<Box<dyn BufRead>, Error> {
+        if end.map_or(false, |end| end <= start) {
+            return Ok(Box::new(BufReader::new(Cursor::new(Vec::<u8>::new()))));
+        }
+
+        let key = self.key(path);
+        let range = range_header(start, end);
+
+        let resp = match with_retry(&self.retry_policy, || {
+            self.client.get_object().bucket(self.bucket.clone()).key(&key).range(range.clone()).send()
+        }).await {
+            Ok(out) => out,
+            Err(e) => {
+                eprintln!("Failure reading the file range: {}, Bucket: {}, Key: {}", e, self.bucket, key);
+                return Err(e);
+            }
+        };
+
+        match resp.body.collect().await {
+            Ok(out) => {
+                let data = out.to_vec();
+                let buf = BufReader::new(Cursor::new(data));
+
+                Ok(Box::new(buf))
+            },
+            Err(e) => {
+                eprintln!("Failure decoding the file range: {:?}", e);
+                Err(Error::new(e.to_string().as_ref()))
+            }
+        }
+    }
+
+    async fn read_stream(&self, path: &str) -> Result<Pin<Box<dyn AsyncRead + Send>>, Error> {
+        let key = self.key(path);
+        let resp = match with_retry(&self.retry_policy, || {
+            self.client.get_object().bucket(self.bucket.clone()).key(&key).send()
+        }).await {
+            Ok(out) => out,
+            Err(e) => {
+                eprintln!("Failure reading the file stream: {}, Bucket: {}, Key: {}", e, self.bucket, key);
+                return Err(e);
+            }
+        };
+
+        Ok(Box::pin(resp.body.into_async_read()))
+    }
+
+    async fn write_stream(&self, path: &str, mut reader: Pin<Box<dyn AsyncRead + Send>>) -> Result<(), Error> {
+        let key = self.key(path);
+        let chunk_size = self.multipart_chunk_size.max(1);
+
+        let mut first_chunk = vec![0u8; chunk_size];
+        let mut filled = 0;
+        while filled < chunk_size {
+            let read = reader.read(&mut first_chunk[filled..]).await?;
+            if read == 0 {
+                break;
+            }
+            filled += read;
+        }
+        first_chunk.truncate(filled);
+
+        if filled < chunk_size {
+            // The stream ended within the first chunk: a single put_object is
+            // cheaper than paying for a 3-round-trip multipart upload.
+            return self.write_file(path.to_string(), first_chunk).await;
+        }
+
+        self.write_stream_multipart(&key, first_chunk, reader).await
+    }
+
+    async fn presign(&self, path: &str, method: PresignMethod, expires_in: Duration) -> Result<String, Error> {
+        let key = self.key(path);
+        let presigning_config = aws_sdk_s3::presigning::PresigningConfig::expires_in(expires_in)?;
+
+        let presigned = match method {
+            PresignMethod::Get => {
+                self.client.get_object()
+                    .bucket(self.bucket.clone())
+                    .key(&key)
+                    .presigned(presigning_config)
+                    .await?
+            }
+            PresignMethod::Put => {
+                self.client.put_object()
+                    .bucket(self.bucket.clone())
+                    .key(&key)
+                    .presigned(presigning_config)
+                    .await?
+            }
+        };
+
+        Ok(presigned.uri().to_string())
+    }
+
     async fn move_file(&self, file_name: &str, ending_path: &str, delete: bool) -> Result<(), Error> {
 
         let split_file_location: Vec<&str> = ending_path.split("/").collect::<Vec<&str>>();
         let ending_file_location = split_file_location.first().expect("You didn't supply a / with your request. It should be {bucket}/{file_name}");
-        
-        println!("Moving file from {:?} to {:?}", [&self.bucket, "/", file_name].join(""), ending_path);
-        
-        self.client
-        .copy_object()
-        .copy_source([&self.bucket, "/", file_name].join(""))
-        .bucket(*ending_file_location)
-        .key(file_name)
-        .send()
-        .await?;
 
-        if delete {
+        let source_key = self.key(file_name);
+        let copy_source = [&self.bucket, "/", &source_key].join("");
+        println!("Moving file from {:?} to {:?}", copy_source, ending_path);
+
+        with_retry(&self.retry_policy, || {
             self.client
-            .delete_object()
-            .bucket(&self.bucket)
-            .key(file_name)
+            .copy_object()
+            .copy_source(&copy_source)
+            .bucket(*ending_file_location)
+            .key(&source_key)
             .send()
-            .await?;
+        }).await?;
+
+        if delete {
+            with_retry(&self.retry_policy, || {
+                self.client.delete_object().bucket(&self.bucket).key(&source_key).send()
+            }).await?;
         }
 
         Ok(())
     }
 
-    /* Path is unused here */
-    async fn read_dir(&self, prefix: &str) -> Result<Vec<FileEntry>, Error> {
-        let resp = self.client
-        .list_objects_v2()
-        .bucket(self.bucket.clone())
-        .prefix(prefix)
-        .send()
-        .await?;
+    async fn read_dir(&self, path: &str) -> Result<Vec<FileEntry>, Error> {
+        let prefix = self.key(path);
 
         let mut entries = Vec::new();
-        for entry in resp.contents.unwrap() {
+        let mut continuation_token: Option<String> = None;
 
-            let file_entry = FileEntry {
-                name: entry.key.unwrap(),
-                size: entry.size.unwrap() as u64,
+        loop {
+            let resp = with_retry(&self.retry_policy, || {
+                let mut request = self.client
+                    .list_objects_v2()
+                    .bucket(self.bucket.clone())
+                    .prefix(&prefix);
+
+                if let Some(token) = &continuation_token {
+                    request = request.continuation_token(token);
+                }
+
+                request.send()
+            }).await?;
+
+            for entry in resp.contents.unwrap_or_default() {
+                let (Some(name), Some(size)) = (entry.key, entry.size) else {
+                    continue;
+                };
+
+                entries.push(FileEntry { name, size: size as u64 });
+            }
+
+            continuation_token = match resp.is_truncated {
+                Some(true) => resp.next_continuation_token,
+                _ => None,
             };
 
-            entries.push(file_entry);
+            if continuation_token.is_none() {
+                break;
+            }
         }
 
-
         Ok(entries)
     }
 
@@ -280,35 +765,88 @@ impl FileProvider for AwsS3FileProvider {
     }
 
     async fn write_file(&self, path: String, contents: Vec<u8>) -> Result<(), Error> {
-        match self.client.put_object()
-        .bucket(self.bucket.clone())
-        .key(&path)
-        .body(ByteStream::from(contents))
-        .send()
-        .await {
-            Ok(_) => {
-                let valu = format!("{} uploaded to {}", path, self.bucket);
-                println!("{}", valu)
-            },
-            Err(e) => {
-                let valu = format!("\n\n{} uploaded failed {:?}\n\n", path, e);
-                println!("{}", valu)
-            }
-        };
+        let key = self.key(&path);
+
+        if contents.len() > self.multipart_chunk_size.max(1) {
+            return self.write_file_multipart(&key, contents).await;
+        }
+
+        with_retry(&self.retry_policy, || {
+            self.client.put_object()
+            .bucket(self.bucket.clone())
+            .key(&key)
+            .body(ByteStream::from(contents.clone()))
+            .send()
+        }).await?;
+
+        println!("{} uploaded to {}", key, self.bucket);
 
         Ok(())
     }
 
     async fn write(&self, path: &str, content: &str) -> Result<(), Error> {
-        unimplemented!()
+        self.write_file(path.to_string(), content.as_bytes().to_vec()).await
     }
 
     async fn delete_file(&self, path: &str) -> Result<(), Error> {
-        unimplemented!()
+        let key = self.key(path);
+
+        with_retry(&self.retry_policy, || {
+            self.client.delete_object().bucket(self.bucket.clone()).key(&key).send()
+        }).await?;
+
+        Ok(())
     }
 
     async fn create_dir(&self, path: &str) -> Result<(), Error> {
-        unimplemented!()
+        let key = self.key(path);
+        let key = if key.ends_with('/') { key } else { format!("{}/", key) };
+
+        with_retry(&self.retry_policy, || {
+            self.client.put_object()
+                .bucket(self.bucket.clone())
+                .key(&key)
+                .body(ByteStream::from_static(b""))
+                .send()
+        }).await?;
+
+        Ok(())
+    }
+
+    async fn delete_dir(&self, path: &str) -> Result<(), Error> {
+        // Match on a trailing slash so this only matches the directory itself,
+        // not sibling keys/directories that merely share the prefix (e.g.
+        // "reports/2024" vs "reports/2024-old/...").
+        let path = if path.ends_with('/') { path.to_string() } else { format!("{}/", path) };
+        let entries = self.read_dir(&path).await?;
+
+        for chunk in entries.chunks(1000) {
+            if chunk.is_empty() {
+                continue;
+            }
+
+            let mut objects = Vec::with_capacity(chunk.len());
+            for entry in chunk {
+                objects.push(ObjectIdentifier::builder().key(&entry.name).build()?);
+            }
+
+            let delete = Delete::builder().set_objects(Some(objects)).build()?;
+
+            let resp = with_retry(&self.retry_policy, || {
+                self.client.delete_objects()
+                    .bucket(self.bucket.clone())
+                    .delete(delete.clone())
+                    .send()
+            }).await?;
+
+            if let Some(errors) = resp.errors {
+                if !errors.is_empty() {
+                    return Err(Error::new(&format!("Failed to delete {} of {} object(s) in {:?}: {:?}", errors.len(), chunk.len(), path, errors)));
+                }
+            }
+        }
+
+        Ok(())
     }
 
     fn get_base_path(&self) -> &str {
@@ -316,3 +854,52 @@ impl FileProvider for AwsS3FileProvider {
     }
 
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn from_str_rejects_uri_without_scheme() {
+        let err = FileSystem::from_str("not-a-uri").await.unwrap_err();
+        assert!(err.to_string().contains("Missing scheme"));
+    }
+
+    #[tokio::test]
+    async fn from_str_rejects_empty_file_path() {
+        let err = FileSystem::from_str("file://").await.unwrap_err();
+        assert!(err.to_string().contains("missing a path"));
+    }
+
+    #[tokio::test]
+    async fn from_str_rejects_unsupported_scheme() {
+        let err = FileSystem::from_str("ftp://example.com").await.unwrap_err();
+        assert!(err.to_string().contains("Unsupported storage URI scheme"));
+    }
+
+    #[tokio::test]
+    async fn from_str_builds_local_provider() {
+        let provider = FileSystem::from_str("file:///tmp/data").await.unwrap();
+        assert_eq!(provider.get_base_path(), "/tmp/data");
+    }
+
+    #[cfg(feature = "aws-s3")]
+    #[tokio::test]
+    async fn from_str_rejects_s3_uri_without_bucket() {
+        let err = FileSystem::from_str("s3://").await.unwrap_err();
+        assert!(err.to_string().contains("missing a bucket name"));
+    }
+
+    #[cfg(feature = "aws-s3")]
+    #[test]
+    fn range_header_end_is_exclusive() {
+        assert_eq!(range_header(0, Some(10)), "bytes=0-9");
+        assert_eq!(range_header(10, Some(20)), "bytes=10-19");
+    }
+
+    #[cfg(feature = "aws-s3")]
+    #[test]
+    fn range_header_without_end_reads_to_eof() {
+        assert_eq!(range_header(5, None), "bytes=5-");
+    }
+}